@@ -2,26 +2,146 @@ use crate::{
     lex::{self, profile::Token, Span},
     Error,
 };
-use anyhow::{bail, Result};
+use anyhow::Result;
+use semver::VersionReq;
 use std::borrow::Cow;
 
 type Tokenizer<'a> = lex::Tokenizer<'a, Token>;
 
+/// Result of a parse step that reports failures as a full [`Diagnostic`]
+/// (span, secondary labels, notes) instead of collapsing them into a flat
+/// message up front; callers that don't care about the extra detail can
+/// still convert to `anyhow::Result` at the boundary with `?`/`map_err`.
+type PResult<T> = std::result::Result<T, Diagnostic>;
+
 pub struct Ast<'a> {
+    pub uses: Vec<Use<'a>>,
     pub items: Vec<Item<'a>>,
 }
 
 impl<'a> Ast<'a> {
     pub fn parse(input: &'a str) -> Result<Self> {
+        Self::parse_inner(input).map_err(Into::into)
+    }
+
+    fn parse_inner(input: &'a str) -> PResult<Self> {
+        let mut lexer = Tokenizer::new(input);
+        let mut uses = Vec::new();
+        let mut items = Vec::new();
+
+        while lexer.peek()?.is_some() {
+            Self::parse_step(&mut lexer, &mut uses, &mut items)?;
+        }
+
+        Ok(Ast { uses, items })
+    }
+
+    /// Parses the next `use` or item off `lexer`, appending it to `uses` or
+    /// `items`. Shared by [`Ast::parse_inner`] and [`Ast::parse_recover`] so
+    /// the two only need to agree on item dispatch in one place; adding a
+    /// new item keyword here is automatically picked up by error recovery
+    /// too.
+    fn parse_step(
+        lexer: &mut Tokenizer<'a>,
+        uses: &mut Vec<Use<'a>>,
+        items: &mut Vec<Item<'a>>,
+    ) -> PResult<()> {
+        let docs = Docs::parse(lexer)?;
+
+        match lexer.peek()? {
+            Some((_span, Token::Use)) => {
+                if let Some(first) = items.first() {
+                    let (span, _) = lexer.peek()?.unwrap();
+                    return Err(Diagnostic::error(span, "`use` must appear before other items")
+                        .with_secondary(first.span(), "first item is here"));
+                }
+                uses.push(Use::parse(lexer)?);
+            }
+            _ => items.push(Item::parse(lexer, docs)?),
+        }
+
+        Ok(())
+    }
+
+    /// Parses `input` like [`Ast::parse`], but never stops at the first
+    /// malformed item: each failure is recorded as a `Diagnostic` and
+    /// parsing resynchronizes at the next item keyword, so a caller gets
+    /// every problem in the file in one pass instead of one per compile.
+    pub fn parse_recover(input: &'a str) -> (Self, Vec<Diagnostic>) {
         let mut lexer = Tokenizer::new(input);
+        let mut uses = Vec::new();
         let mut items = Vec::new();
+        let mut diagnostics = Vec::new();
+
+        loop {
+            let start = match lexer.peek() {
+                Ok(Some((span, _))) => span,
+                Ok(None) | Err(_) => break,
+            };
 
-        while lexer.clone().next()?.is_some() {
-            let docs = Docs::parse(&mut lexer)?;
-            items.push(Item::parse(&mut lexer, docs)?);
+            if let Err(diag) = Self::parse_step(&mut lexer, &mut uses, &mut items) {
+                diagnostics.push(diag);
+                Self::resync(&mut lexer, start);
+            }
         }
 
-        Ok(Ast { items })
+        (Ast { uses, items }, diagnostics)
+    }
+
+    /// Skips tokens until the next item keyword (`use`/`extend`/`provide`/
+    /// `require`/`implement`/`interface`) or EOF, then resumes parsing from
+    /// there. `start` is the span the failed attempt began at: some parse
+    /// steps fail without consuming anything (e.g. `Item::parse`'s keyword
+    /// dispatch, which only peeks), while others fail after consuming one or
+    /// more tokens (e.g. `Id::parse` consuming a wrong token before erroring).
+    /// Only force-advance past `start` in the former case — if the lexer has
+    /// already moved on, it may already be sitting on the next item's
+    /// boundary keyword, and force-skipping would eat it. Peeking first
+    /// (rather than unconditionally skipping first) still guarantees
+    /// progress: if nothing was consumed, `start` is forced past; if
+    /// something was consumed, that consumption was itself progress.
+    /// Operates on lexed tokens rather than raw text so a keyword-looking
+    /// sequence inside a string literal is never mistaken for a recovery
+    /// boundary.
+    fn resync(lexer: &mut Tokenizer<'a>, start: Span) {
+        let made_no_progress = matches!(lexer.peek(), Ok(Some((span, _))) if span.start == start.start);
+        if made_no_progress {
+            let _ = lexer.next();
+        }
+
+        loop {
+            match lexer.peek() {
+                Ok(Some((
+                    _,
+                    Token::Use
+                    | Token::Extend
+                    | Token::Provide
+                    | Token::Require
+                    | Token::Implement
+                    | Token::Interface,
+                ))) => break,
+                Ok(Some(_)) => {
+                    let _ = lexer.next();
+                }
+                Ok(None) | Err(_) => break,
+            }
+        }
+    }
+}
+
+pub struct Use<'a> {
+    pub span: Span,
+    pub target: Id<'a>,
+}
+
+impl<'a> Use<'a> {
+    fn parse(tokens: &mut Tokenizer<'a>) -> PResult<Self> {
+        let mut span = tokens.expect(Token::Use)?;
+        let target = Id::parse(tokens)?;
+
+        span.end = target.span.end;
+
+        Ok(Self { span, target })
     }
 }
 
@@ -31,7 +151,7 @@ pub struct Id<'a> {
 }
 
 impl<'a> Id<'a> {
-    fn parse(tokens: &mut Tokenizer<'a>) -> Result<Self> {
+    fn parse(tokens: &mut Tokenizer<'a>) -> PResult<Self> {
         match tokens.next()? {
             Some((span, Token::Id)) => Ok(Id {
                 name: tokens.get_span(span).into(),
@@ -43,30 +163,356 @@ impl<'a> Id<'a> {
             }),
             other => {
                 let (span, msg) = tokens.format_expected_error("an identifier or string", other);
-                bail!(Error { span, msg })
+                Err(Diagnostic::error(span, msg))
             }
         }
     }
 }
 
+/// A reference to another profile's interface, optionally constrained to a
+/// semver range (e.g. `wasi:clocks@0.2.1` or `foo@^0.3`), as used by
+/// `provide`/`require`/`extend` and the string operands of `implement`.
+pub struct InterfaceRef<'a> {
+    pub name: Id<'a>,
+    pub version: Option<VersionConstraint>,
+    pub span: Span,
+}
+
+impl<'a> InterfaceRef<'a> {
+    fn parse(tokens: &mut Tokenizer<'a>) -> PResult<Self> {
+        let name = Id::parse(tokens)?;
+        let mut span = name.span;
+
+        let version = if matches!(tokens.peek()?, Some((_, Token::At))) {
+            tokens.expect(Token::At)?;
+            let version_span = tokens.expect(Token::Version)?;
+            span.end = version_span.end;
+            Some(Self::parse_version_req(tokens.get_span(version_span), version_span)?)
+        } else {
+            None
+        };
+
+        Ok(Self { name, version, span })
+    }
+
+    /// Parses an `InterfaceRef` out of a `"name@version"` string literal, for
+    /// `implement`'s string operands, which carry the version inline rather
+    /// than as a separate `@` token.
+    fn from_str_lit(tokens: &Tokenizer<'a>, span: Span) -> PResult<Self> {
+        let text = tokens.parse_str(span);
+
+        match text.find('@') {
+            Some(at) => {
+                let version = Self::parse_version_req(&text[at + 1..], span)?;
+                let name = text[..at].to_string();
+                Ok(Self {
+                    name: Id {
+                        name: name.into(),
+                        span,
+                    },
+                    version: Some(version),
+                    span,
+                })
+            }
+            None => Ok(Self {
+                name: Id { name: text, span },
+                version: None,
+                span,
+            }),
+        }
+    }
+
+    fn parse_version_req(text: &str, span: Span) -> PResult<VersionConstraint> {
+        VersionConstraint::parse(text)
+            .map_err(|err| Diagnostic::error(span, format!("invalid version requirement: {}", err)))
+    }
+}
+
+/// A parsed version requirement, kept as its underlying comparator list
+/// (rather than treating `semver::VersionReq` as opaque) so that two
+/// `require`s naming the same interface can later be checked for
+/// conflicts with [`VersionConstraint::intersects`].
+pub struct VersionConstraint {
+    comparators: Vec<semver::Comparator>,
+}
+
+impl VersionConstraint {
+    fn parse(text: &str) -> std::result::Result<Self, semver::Error> {
+        VersionReq::parse(text).map(|req| Self {
+            comparators: req.comparators,
+        })
+    }
+
+    pub fn matches(&self, version: &semver::Version) -> bool {
+        self.comparators.iter().all(|cmp| Self::comparator_matches(cmp, version))
+    }
+
+    fn comparator_matches(cmp: &semver::Comparator, version: &semver::Version) -> bool {
+        // `Comparator` doesn't expose a standalone `matches`, so round-trip
+        // through a single-comparator `VersionReq`, which does.
+        VersionReq {
+            comparators: vec![cmp.clone()],
+        }
+        .matches(version)
+    }
+
+    /// Reports whether `self` and `other` could both be satisfied by some
+    /// version, i.e. whether requiring both at once on the same interface is
+    /// satisfiable. Each side is reduced to an inclusive-lower/exclusive-upper
+    /// interval and the intervals are checked for overlap.
+    ///
+    /// This only handles a single comparator per side (covers the
+    /// `@x.y.z`/`@=x.y.z`/`@^x.y.z`/`@~x.y.z`/`@>=x.y.z`-style forms this
+    /// grammar actually produces); a requirement built from several
+    /// comma-separated comparators conservatively reports `true`, since
+    /// combining interval arithmetic across a whole comparator set needs
+    /// more machinery than a single profile parser otherwise needs.
+    pub fn intersects(&self, other: &Self) -> bool {
+        let (Some(a), Some(b)) = (self.comparators.first(), other.comparators.first()) else {
+            return true;
+        };
+        if self.comparators.len() > 1 || other.comparators.len() > 1 {
+            return true;
+        }
+
+        let (a_min, a_max) = Self::bounds(a);
+        let (b_min, b_max) = Self::bounds(b);
+
+        let lower = a_min.max(b_min);
+        let upper = match (a_max, b_max) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (Some(a), None) => Some(a),
+            (None, Some(b)) => Some(b),
+            (None, None) => None,
+        };
+
+        match upper {
+            Some(upper) => lower < upper,
+            None => true,
+        }
+    }
+
+    /// The inclusive lower bound and optional exclusive upper bound implied
+    /// by a single comparator.
+    fn bounds(cmp: &semver::Comparator) -> (semver::Version, Option<semver::Version>) {
+        use semver::Op;
+
+        let min = semver::Version::new(cmp.major, cmp.minor.unwrap_or(0), cmp.patch.unwrap_or(0));
+
+        match cmp.op {
+            Op::Exact => (min.clone(), Some(Self::bump_patch(&min))),
+            Op::Greater => (Self::bump_patch(&min), None),
+            Op::GreaterEq => (min, None),
+            Op::Less => (semver::Version::new(0, 0, 0), Some(min)),
+            Op::LessEq => (semver::Version::new(0, 0, 0), Some(Self::bump_patch(&min))),
+            Op::Tilde => {
+                let max = match cmp.minor {
+                    Some(minor) => semver::Version::new(cmp.major, minor + 1, 0),
+                    None => semver::Version::new(cmp.major + 1, 0, 0),
+                };
+                (min, Some(max))
+            }
+            Op::Caret => {
+                // Caret only narrows to the leftmost nonzero component, and
+                // an *absent* component (vs. present-and-zero) still counts
+                // as "not yet narrowed": `^0` is `<1.0.0`, `^0.0` is
+                // `<0.1.0`, and only `^0.0.K` (minor *and* patch present)
+                // narrows to `<0.0.(K+1)`.
+                let max = if cmp.major > 0 {
+                    semver::Version::new(cmp.major + 1, 0, 0)
+                } else {
+                    match (cmp.minor, cmp.patch) {
+                        (None, _) => semver::Version::new(1, 0, 0),
+                        (Some(0), None) => semver::Version::new(0, 1, 0),
+                        (Some(0), Some(patch)) => semver::Version::new(0, 0, patch + 1),
+                        (Some(minor), _) => semver::Version::new(0, minor + 1, 0),
+                    }
+                };
+                (min, Some(max))
+            }
+            // Wildcard and any future comparator kinds: treat as unbounded
+            // above rather than risk excluding a version that should match.
+            _ => (min, None),
+        }
+    }
+
+    fn bump_patch(version: &semver::Version) -> semver::Version {
+        semver::Version::new(version.major, version.minor, version.patch + 1)
+    }
+}
+
+/// Severity of a [`Diagnostic`], controlling how it's labeled when rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A single labeled span within a [`Diagnostic`], rendered as a caret
+/// underline beneath the span with `message` printed alongside it.
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+}
+
+impl Label {
+    pub fn new(span: Span, message: impl Into<String>) -> Self {
+        Self {
+            span,
+            message: message.into(),
+        }
+    }
+}
+
+/// A rich diagnostic: a primary labeled span plus any number of secondary
+/// labels and trailing notes, modeled after the `codespan-reporting` crate.
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub primary: Label,
+    pub secondary: Vec<Label>,
+    pub notes: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn error(span: Span, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            primary: Label::new(span, message),
+            secondary: Vec::new(),
+            notes: Vec::new(),
+        }
+    }
+
+    pub fn with_secondary(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.secondary.push(Label::new(span, message));
+        self
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    /// Renders this diagnostic against `source`, printing the offending
+    /// line(s) with a caret underline beneath the primary span, inline
+    /// secondary labels, and trailing notes.
+    pub fn render(&self, source: &str) -> String {
+        let mut out = String::new();
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        out.push_str(&format!("{}: {}\n", severity, self.primary.message));
+
+        out.push_str(&Self::render_label(source, &self.primary));
+        for label in &self.secondary {
+            out.push_str(&Self::render_label(source, label));
+        }
+        for note in &self.notes {
+            out.push_str(&format!("note: {}\n", note));
+        }
+
+        out
+    }
+
+    fn render_label(source: &str, label: &Label) -> String {
+        let (line, col) = Self::line_col(source, label.span.start);
+        let line_text = source.lines().nth(line).unwrap_or("");
+        let underline_len = (label.span.end - label.span.start).max(1);
+
+        format!(
+            "  --> line {}, column {}\n   | {}\n   | {}{} {}\n",
+            line + 1,
+            col + 1,
+            line_text,
+            " ".repeat(col),
+            "^".repeat(underline_len),
+            label.message,
+        )
+    }
+
+    /// Maps a byte offset into `source` to a zero-indexed (line, column).
+    fn line_col(source: &str, offset: usize) -> (usize, usize) {
+        let mut line = 0;
+        let mut line_start = 0;
+        for (i, ch) in source.char_indices() {
+            if i >= offset {
+                break;
+            }
+            if ch == '\n' {
+                line += 1;
+                line_start = i + 1;
+            }
+        }
+        (line, offset - line_start)
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.primary.message)
+    }
+}
+
+impl std::fmt::Debug for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Diagnostic")
+            .field("message", &self.primary.message)
+            .finish()
+    }
+}
+
+impl std::error::Error for Diagnostic {}
+
+/// Lets parse steps that return [`PResult`] use `?` directly against the
+/// underlying tokenizer (which raises the flat [`Error`]) without losing the
+/// span: downcasts back to `Diagnostic` (as returned by [`Ast::parse`]) or to
+/// `Error` when possible instead of flattening to a message-only diagnostic.
+impl From<anyhow::Error> for Diagnostic {
+    fn from(err: anyhow::Error) -> Self {
+        let err = match err.downcast::<Diagnostic>() {
+            Ok(diag) => return diag,
+            Err(err) => err,
+        };
+        match err.downcast::<Error>() {
+            Ok(Error { span, msg }) => Diagnostic::error(span, msg),
+            Err(err) => Diagnostic::error(Span { start: 0, end: 0 }, err.to_string()),
+        }
+    }
+}
+
 pub enum Item<'a> {
     Extend(Extend<'a>),
     Provide(Provide<'a>),
     Require(Require<'a>),
     Implement(Implement<'a>),
+    Interface(Interface<'a>),
 }
 
 impl<'a> Item<'a> {
-    fn parse(tokens: &mut Tokenizer<'a>, docs: Docs<'a>) -> Result<Self> {
-        match tokens.clone().next()? {
+    fn span(&self) -> Span {
+        match self {
+            Item::Extend(e) => e.span,
+            Item::Provide(p) => p.span,
+            Item::Require(r) => r.span,
+            Item::Implement(i) => i.span,
+            Item::Interface(i) => i.span,
+        }
+    }
+
+    fn parse(tokens: &mut Tokenizer<'a>, docs: Docs<'a>) -> PResult<Self> {
+        match tokens.peek()? {
             Some((_span, Token::Extend)) => Extend::parse(tokens).map(Item::Extend),
             Some((_span, Token::Provide)) => Provide::parse(tokens, docs).map(Item::Provide),
             Some((_span, Token::Require)) => Require::parse(tokens, docs).map(Item::Require),
             Some((_span, Token::Implement)) => Implement::parse(tokens, docs).map(Item::Implement),
+            Some((_span, Token::Interface)) => Interface::parse(tokens, docs).map(Item::Interface),
             other => {
-                let (span, msg) = tokens
-                    .format_expected_error("`extend`, `provide`, `require`, or `implement`", other);
-                bail!(Error { span, msg })
+                let (span, msg) = tokens.format_expected_error(
+                    "`extend`, `provide`, `require`, `implement`, or `interface`",
+                    other,
+                );
+                Err(Diagnostic::error(span, msg))
             }
         }
     }
@@ -77,17 +523,20 @@ pub struct Docs<'a> {
 }
 
 impl<'a> Docs<'a> {
-    fn parse(tokens: &mut Tokenizer<'a>) -> Result<Self> {
+    fn parse(tokens: &mut Tokenizer<'a>) -> PResult<Self> {
         let mut docs = Self { docs: Vec::new() };
-        let mut clone = tokens.clone();
 
-        while let Some((span, token)) = clone.next_raw()? {
+        while let Some((span, token)) = tokens.peek_raw()? {
             match token {
-                Token::Whitespace => {}
-                Token::Comment => docs.docs.push(tokens.get_span(span).into()),
+                Token::Whitespace => {
+                    tokens.next_raw()?;
+                }
+                Token::Comment => {
+                    docs.docs.push(tokens.get_span(span).into());
+                    tokens.next_raw()?;
+                }
                 _ => break,
-            };
-            *tokens = clone.clone();
+            }
         }
 
         Ok(docs)
@@ -96,13 +545,13 @@ impl<'a> Docs<'a> {
 
 pub struct Extend<'a> {
     pub span: Span,
-    pub profile: Id<'a>,
+    pub profile: InterfaceRef<'a>,
 }
 
 impl<'a> Extend<'a> {
-    fn parse(tokens: &mut Tokenizer<'a>) -> Result<Self> {
+    fn parse(tokens: &mut Tokenizer<'a>) -> PResult<Self> {
         let mut span = tokens.expect(Token::Extend)?;
-        let profile = Id::parse(tokens)?;
+        let profile = InterfaceRef::parse(tokens)?;
 
         span.end = profile.span.end;
 
@@ -113,13 +562,13 @@ impl<'a> Extend<'a> {
 pub struct Provide<'a> {
     pub docs: Docs<'a>,
     pub span: Span,
-    pub interface: Id<'a>,
+    pub interface: InterfaceRef<'a>,
 }
 
 impl<'a> Provide<'a> {
-    fn parse(tokens: &mut Tokenizer<'a>, docs: Docs<'a>) -> Result<Self> {
+    fn parse(tokens: &mut Tokenizer<'a>, docs: Docs<'a>) -> PResult<Self> {
         let mut span = tokens.expect(Token::Provide)?;
-        let interface = Id::parse(tokens)?;
+        let interface = InterfaceRef::parse(tokens)?;
 
         span.end = interface.span.end;
 
@@ -134,13 +583,13 @@ impl<'a> Provide<'a> {
 pub struct Require<'a> {
     pub docs: Docs<'a>,
     pub span: Span,
-    pub interface: Id<'a>,
+    pub interface: InterfaceRef<'a>,
 }
 
 impl<'a> Require<'a> {
-    fn parse(tokens: &mut Tokenizer<'a>, docs: Docs<'a>) -> Result<Self> {
+    fn parse(tokens: &mut Tokenizer<'a>, docs: Docs<'a>) -> PResult<Self> {
         let mut span = tokens.expect(Token::Require)?;
-        let interface = Id::parse(tokens)?;
+        let interface = InterfaceRef::parse(tokens)?;
 
         span.end = interface.span.end;
 
@@ -155,15 +604,31 @@ impl<'a> Require<'a> {
 pub struct Implement<'a> {
     pub docs: Docs<'a>,
     pub span: Span,
-    pub interface: Cow<'a, str>,
-    pub component: Cow<'a, str>,
+    pub interface: InterfaceRef<'a>,
+    pub component: InterfaceRef<'a>,
 }
 
 impl<'a> Implement<'a> {
-    fn parse(tokens: &mut Tokenizer<'a>, docs: Docs<'a>) -> Result<Self> {
+    fn parse(tokens: &mut Tokenizer<'a>, docs: Docs<'a>) -> PResult<Self> {
         let mut span = tokens.expect(Token::Implement)?;
         let interface = tokens.expect(Token::StrLit)?;
-        tokens.expect(Token::With)?;
+
+        if !matches!(tokens.peek()?, Some((_, Token::With))) {
+            let other = tokens.peek()?;
+            let (error_span, msg) = tokens.format_expected_error("`with`", other);
+            return Err(Diagnostic::error(error_span, msg)
+                .with_secondary(interface, "interface named here")
+                .with_note("expected the form `implement \"<interface>\" with \"<component>\"`"));
+        }
+        let with_span = tokens.expect(Token::With)?;
+
+        if !matches!(tokens.peek()?, Some((_, Token::StrLit))) {
+            let other = tokens.peek()?;
+            let (error_span, msg) = tokens.format_expected_error("a component name", other);
+            return Err(Diagnostic::error(error_span, msg)
+                .with_secondary(with_span, "`with` is here")
+                .with_note("expected the form `implement \"<interface>\" with \"<component>\"`"));
+        }
         let component = tokens.expect(Token::StrLit)?;
 
         span.end = component.end;
@@ -171,8 +636,371 @@ impl<'a> Implement<'a> {
         Ok(Self {
             docs,
             span,
-            interface: tokens.parse_str(interface).into(),
-            component: tokens.parse_str(component).into(),
+            interface: InterfaceRef::from_str_lit(tokens, interface)?,
+            component: InterfaceRef::from_str_lit(tokens, component)?,
+        })
+    }
+}
+
+/// An inline interface definition, making a profile self-describing instead
+/// of referencing interfaces purely by name:
+///
+/// ```text
+/// interface file {
+///     function new accepts(path) returns(None);
+/// }
+/// ```
+pub struct Interface<'a> {
+    pub docs: Docs<'a>,
+    pub span: Span,
+    pub name: Id<'a>,
+    pub functions: Vec<Function<'a>>,
+}
+
+impl<'a> Interface<'a> {
+    fn parse(tokens: &mut Tokenizer<'a>, docs: Docs<'a>) -> PResult<Self> {
+        let mut span = tokens.expect(Token::Interface)?;
+        let name = Id::parse(tokens)?;
+        tokens.expect(Token::LeftBrace)?;
+
+        let mut functions = Vec::new();
+        while !matches!(tokens.peek()?, Some((_, Token::RightBrace)) | None) {
+            let fn_docs = Docs::parse(tokens)?;
+            functions.push(Function::parse(tokens, fn_docs)?);
+        }
+
+        let end = tokens.expect(Token::RightBrace)?;
+        span.end = end.end;
+
+        Ok(Self {
+            docs,
+            span,
+            name,
+            functions,
+        })
+    }
+}
+
+/// A single function signature inside an [`Interface`], written either as
+/// `function <name> accepts(<types>) returns(<type>|None);` or as
+/// `function <name>(<name>: <type>, ...) -> <type>;`.
+pub struct Function<'a> {
+    pub docs: Docs<'a>,
+    pub span: Span,
+    pub name: Id<'a>,
+    pub params: Vec<(Id<'a>, TypeRef<'a>)>,
+    pub result: Option<TypeRef<'a>>,
+}
+
+impl<'a> Function<'a> {
+    fn parse(tokens: &mut Tokenizer<'a>, docs: Docs<'a>) -> PResult<Self> {
+        let mut span = tokens.expect(Token::Function)?;
+        let name = Id::parse(tokens)?;
+
+        let (params, result) = if matches!(tokens.peek()?, Some((_, Token::Accepts))) {
+            Self::parse_accepts_returns(tokens)?
+        } else {
+            Self::parse_arrow_signature(tokens)?
+        };
+
+        let end = tokens.expect(Token::Semicolon)?;
+        span.end = end.end;
+
+        Ok(Self {
+            docs,
+            span,
+            name,
+            params,
+            result,
         })
     }
+
+    /// `accepts(<type>, ...) returns(<type>|None)`. Parameters are unnamed
+    /// here, so each one is given a positional `arg<N>` name.
+    fn parse_accepts_returns(
+        tokens: &mut Tokenizer<'a>,
+    ) -> PResult<(Vec<(Id<'a>, TypeRef<'a>)>, Option<TypeRef<'a>>)> {
+        tokens.expect(Token::Accepts)?;
+        tokens.expect(Token::LeftParen)?;
+
+        let mut params = Vec::new();
+        while !matches!(tokens.peek()?, Some((_, Token::RightParen))) {
+            let ty = TypeRef::parse(tokens)?;
+            let name = Id {
+                name: format!("arg{}", params.len()).into(),
+                span: ty.span,
+            };
+            params.push((name, ty));
+
+            if matches!(tokens.peek()?, Some((_, Token::Comma))) {
+                tokens.expect(Token::Comma)?;
+            } else {
+                break;
+            }
+        }
+        tokens.expect(Token::RightParen)?;
+
+        tokens.expect(Token::Returns)?;
+        tokens.expect(Token::LeftParen)?;
+        let result = Self::parse_result(tokens)?;
+        tokens.expect(Token::RightParen)?;
+
+        Ok((params, result))
+    }
+
+    /// `(<name>: <type>, ...) -> <type>`, with the `-> <type>` suffix
+    /// optional when the function returns nothing.
+    fn parse_arrow_signature(
+        tokens: &mut Tokenizer<'a>,
+    ) -> PResult<(Vec<(Id<'a>, TypeRef<'a>)>, Option<TypeRef<'a>>)> {
+        tokens.expect(Token::LeftParen)?;
+
+        let mut params = Vec::new();
+        while !matches!(tokens.peek()?, Some((_, Token::RightParen))) {
+            let name = Id::parse(tokens)?;
+            tokens.expect(Token::Colon)?;
+            let ty = TypeRef::parse(tokens)?;
+            params.push((name, ty));
+
+            if matches!(tokens.peek()?, Some((_, Token::Comma))) {
+                tokens.expect(Token::Comma)?;
+            } else {
+                break;
+            }
+        }
+        tokens.expect(Token::RightParen)?;
+
+        let result = if matches!(tokens.peek()?, Some((_, Token::Arrow))) {
+            tokens.expect(Token::Arrow)?;
+            Some(TypeRef::parse(tokens)?)
+        } else {
+            None
+        };
+
+        Ok((params, result))
+    }
+
+    /// Parses a `returns(...)` operand, where the bare identifier `None`
+    /// means the function has no result.
+    fn parse_result(tokens: &mut Tokenizer<'a>) -> PResult<Option<TypeRef<'a>>> {
+        let id = Id::parse(tokens)?;
+        if id.name == "None" {
+            Ok(None)
+        } else {
+            Ok(Some(TypeRef::from_id(id)))
+        }
+    }
+}
+
+/// A type reference in a [`Function`] signature: either one of a handful of
+/// builtin scalar types or a name resolved against the enclosing profile.
+pub struct TypeRef<'a> {
+    pub kind: TypeRefKind<'a>,
+    pub span: Span,
+}
+
+pub enum TypeRefKind<'a> {
+    Bool,
+    U32,
+    S32,
+    Float32,
+    Float64,
+    String,
+    Named(Id<'a>),
+}
+
+impl<'a> TypeRef<'a> {
+    fn parse(tokens: &mut Tokenizer<'a>) -> PResult<Self> {
+        let id = Id::parse(tokens)?;
+        Ok(Self::from_id(id))
+    }
+
+    fn from_id(id: Id<'a>) -> Self {
+        let span = id.span;
+        let kind = match &*id.name {
+            "bool" => TypeRefKind::Bool,
+            "u32" => TypeRefKind::U32,
+            "s32" => TypeRefKind::S32,
+            "float32" => TypeRefKind::Float32,
+            "float64" => TypeRefKind::Float64,
+            "string" => TypeRefKind::String,
+            _ => TypeRefKind::Named(id),
+        };
+
+        Self { kind, span }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn use_before_other_items_parses() {
+        let ast = Ast::parse("use foo\nextend bar").unwrap();
+        assert_eq!(ast.uses.len(), 1);
+        assert_eq!(ast.items.len(), 1);
+    }
+
+    #[test]
+    fn use_after_other_items_is_rejected() {
+        let err = Ast::parse("extend bar\nuse foo").unwrap_err();
+        let diag = Diagnostic::from(err);
+        assert_eq!(diag.primary.message, "`use` must appear before other items");
+        assert_eq!(diag.secondary.len(), 1);
+        assert_eq!(diag.secondary[0].message, "first item is here");
+    }
+
+    #[test]
+    fn implement_missing_with_keyword_is_rejected() {
+        let err = Ast::parse("implement \"a\";").unwrap_err();
+        let diag = Diagnostic::from(err);
+        assert_eq!(diag.secondary.len(), 1);
+        assert_eq!(diag.secondary[0].message, "interface named here");
+        assert_eq!(diag.notes.len(), 1);
+    }
+
+    #[test]
+    fn implement_missing_component_after_with_is_rejected() {
+        let err = Ast::parse("implement \"a\" with ;").unwrap_err();
+        let diag = Diagnostic::from(err);
+        assert_eq!(diag.secondary.len(), 1);
+        assert_eq!(diag.secondary[0].message, "`with` is here");
+        assert_eq!(diag.notes.len(), 1);
+    }
+
+    #[test]
+    fn interface_ref_parses_caret_and_tilde_version_requirements() {
+        let ast = Ast::parse("provide foo@^1.2.3\nrequire bar@~1.2").unwrap();
+
+        let provide_version = match &ast.items[0] {
+            Item::Provide(p) => p.interface.version.as_ref().unwrap(),
+            _ => panic!("expected a provide item"),
+        };
+        assert!(provide_version.matches(&semver::Version::new(1, 2, 3)));
+        assert!(!provide_version.matches(&semver::Version::new(2, 0, 0)));
+
+        let require_version = match &ast.items[1] {
+            Item::Require(r) => r.interface.version.as_ref().unwrap(),
+            _ => panic!("expected a require item"),
+        };
+        assert!(require_version.matches(&semver::Version::new(1, 2, 5)));
+        assert!(!require_version.matches(&semver::Version::new(1, 3, 0)));
+    }
+
+    #[test]
+    fn interface_ref_from_str_lit_splits_name_and_version() {
+        let ast = Ast::parse("implement \"foo@^1.0.0\" with \"bar\"").unwrap();
+
+        match &ast.items[0] {
+            Item::Implement(i) => {
+                assert_eq!(&*i.interface.name.name, "foo");
+                assert!(i
+                    .interface
+                    .version
+                    .as_ref()
+                    .unwrap()
+                    .matches(&semver::Version::new(1, 5, 0)));
+                assert_eq!(&*i.component.name.name, "bar");
+                assert!(i.component.version.is_none());
+            }
+            _ => panic!("expected an implement item"),
+        }
+    }
+
+    #[test]
+    fn intersects_true_for_overlapping_requirements() {
+        let a = VersionConstraint::parse("^1.2.0").unwrap();
+        let b = VersionConstraint::parse(">=1.5.0").unwrap();
+        assert!(a.intersects(&b));
+    }
+
+    #[test]
+    fn intersects_false_for_disjoint_requirements() {
+        let a = VersionConstraint::parse("^1.0.0").unwrap();
+        let b = VersionConstraint::parse("^2.0.0").unwrap();
+        assert!(!a.intersects(&b));
+    }
+
+    #[test]
+    fn intersects_handles_bare_caret_zero_edge_cases() {
+        // `^0` is `>=0.0.0, <1.0.0`, not `<0.0.1`.
+        let bare_zero = VersionConstraint::parse("^0").unwrap();
+        let overlapping = VersionConstraint::parse(">=0.9.0").unwrap();
+        assert!(bare_zero.intersects(&overlapping));
+
+        // `^0.0` is `>=0.0.0, <0.1.0`, not `<0.0.1`.
+        let zero_zero = VersionConstraint::parse("^0.0").unwrap();
+        let disjoint = VersionConstraint::parse(">=0.5.0").unwrap();
+        assert!(!zero_zero.intersects(&disjoint));
+    }
+
+    #[test]
+    fn parse_recover_resyncs_past_garbage_to_the_next_item() {
+        let (ast, diagnostics) = Ast::parse_recover("foo bar baz\nextend qux");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(ast.items.len(), 1);
+    }
+
+    #[test]
+    fn parse_recover_keeps_valid_item_after_mid_item_failure() {
+        // `Id::parse` consumes the stray `;` before erroring, so the lexer
+        // is already sitting on `provide` (the next item's keyword) by the
+        // time resync runs; it must not eat that keyword too.
+        let (ast, diagnostics) = Ast::parse_recover("extend ;\nprovide foo");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(ast.items.len(), 1);
+    }
+
+    #[test]
+    fn parse_recover_terminates_on_garbage_with_no_item_keyword() {
+        let (ast, diagnostics) = Ast::parse_recover("foo bar baz qux quux");
+        assert!(ast.items.is_empty());
+        assert!(ast.uses.is_empty());
+        assert!(!diagnostics.is_empty());
+    }
+
+    fn only_function(ast: &Ast) -> &Function {
+        match &ast.items[0] {
+            Item::Interface(interface) => &interface.functions[0],
+            _ => panic!("expected an interface item"),
+        }
+    }
+
+    #[test]
+    fn accepts_returns_signature_gives_params_positional_names() {
+        let ast = Ast::parse(
+            "interface file {\n  function new accepts(string, u32) returns(None);\n}",
+        )
+        .unwrap();
+        let function = only_function(&ast);
+        assert_eq!(function.params.len(), 2);
+        assert_eq!(&*function.params[0].0.name, "arg0");
+        assert_eq!(&*function.params[1].0.name, "arg1");
+        assert!(function.result.is_none());
+    }
+
+    #[test]
+    fn arrow_signature_gives_params_their_written_names() {
+        let ast = Ast::parse(
+            "interface file {\n  function new(path: string, mode: u32) -> bool;\n}",
+        )
+        .unwrap();
+        let function = only_function(&ast);
+        assert_eq!(function.params.len(), 2);
+        assert_eq!(&*function.params[0].0.name, "path");
+        assert_eq!(&*function.params[1].0.name, "mode");
+        assert!(matches!(
+            function.result.as_ref().unwrap().kind,
+            TypeRefKind::Bool
+        ));
+    }
+
+    #[test]
+    fn arrow_signature_result_is_optional() {
+        let ast = Ast::parse("interface file {\n  function noop();\n}").unwrap();
+        let function = only_function(&ast);
+        assert!(function.params.is_empty());
+        assert!(function.result.is_none());
+    }
 }